@@ -0,0 +1,125 @@
+//! 18-decimal fixed-point helpers for the matching math
+//!
+//! Square roots and alpha are kept as `u128` values scaled by [`ONE`]; intermediate
+//! products go through `U256` so they cannot overflow.
+
+use ink::primitives::U256;
+
+/// 1.0 in fixed point
+pub const ONE: u128 = 1_000_000_000_000_000_000;
+
+/// Square root of an amount, in fixed point
+pub fn sqrt(amount: u128) -> u128 {
+    // √(x · 10³⁶) = √x · 10¹⁸
+    isqrt(U256::from(amount) * U256::from(ONE) * U256::from(ONE)).as_u128()
+}
+
+/// Product of two fixed-point values, as a plain amount
+pub fn product_to_amount(a: u128, b: u128) -> u128 {
+    to_u128(U256::from(a) * U256::from(b) / (U256::from(ONE) * U256::from(ONE)))
+}
+
+/// Square of a fixed-point value, as a plain amount
+pub fn square_to_amount(value: u128) -> u128 {
+    product_to_amount(value, value)
+}
+
+/// `numerator / denominator` in fixed point, or zero when the denominator is zero
+pub fn ratio(numerator: u128, denominator: u128) -> u128 {
+    if denominator == 0 {
+        return 0;
+    }
+    mul_div(numerator, ONE, denominator)
+}
+
+/// `value · fraction` for a fixed-point `fraction`, rounded down
+pub fn mul_fraction(value: u128, fraction: u128) -> u128 {
+    mul_div(value, fraction, ONE)
+}
+
+/// `a · b / c` without intermediate overflow, rounded down and saturating at `u128::MAX`
+pub fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    to_u128(U256::from(a) * U256::from(b) / U256::from(c))
+}
+
+/// Floor of the square root of a `U256`
+fn isqrt(value: U256) -> U256 {
+    if value.is_zero() {
+        return U256::zero();
+    }
+
+    // Newton's method from an initial guess above the root
+    let mut current = U256::one() << value.bits().div_ceil(2);
+    loop {
+        let next = (current + value / current) >> 1;
+        if next >= current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+fn to_u128(value: U256) -> u128 {
+    if value > U256::from(u128::MAX) {
+        u128::MAX
+    } else {
+        value.as_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn sqrt_of_perfect_squares_is_exact() {
+        assert_eq!(sqrt(0), 0);
+        assert_eq!(sqrt(1), ONE);
+        assert_eq!(sqrt(16), 4 * ONE);
+        assert_eq!(sqrt(1_000_000_000_000), 1_000_000 * ONE);
+    }
+
+    #[test]
+    fn sqrt_distinguishes_small_amounts() {
+        // Integer roots would give 1 for both
+        assert!(sqrt(2) < sqrt(3));
+        assert_eq!(sqrt(2), 1_414_213_562_373_095_048);
+    }
+
+    proptest! {
+        #[test]
+        fn sqrt_is_the_floor_root(amount in 0u128..=1_000_000_000_000_000_000_000_000u128) {
+            let root = U256::from(sqrt(amount));
+            let scaled = U256::from(amount) * U256::from(ONE) * U256::from(ONE);
+            prop_assert!(root * root <= scaled);
+            prop_assert!((root + 1) * (root + 1) > scaled);
+        }
+
+        #[test]
+        fn sqrt_matches_float_reference(amount in 1u128..=1_000_000_000_000_000_000_000_000u128) {
+            let expected = (amount as f64).sqrt() * ONE as f64;
+            let actual = sqrt(amount) as f64;
+            prop_assert!((actual - expected).abs() / expected < 1e-12);
+        }
+
+        #[test]
+        fn square_undoes_sqrt(amount in 0u128..=1_000_000_000_000_000_000_000_000u128) {
+            // Flooring the root loses less than one planck once squared
+            let squared = square_to_amount(sqrt(amount));
+            prop_assert!(squared <= amount);
+            prop_assert!(amount - squared <= 1);
+        }
+
+        #[test]
+        fn mul_fraction_matches_float_reference(
+            value in 1u128..=1_000_000_000_000_000_000_000_000u128,
+            numerator in 0u128..=1_000_000u128,
+        ) {
+            let fraction = ratio(numerator, 1_000_000);
+            let expected = value as f64 * numerator as f64 / 1_000_000.0;
+            let actual = mul_fraction(value, fraction) as f64;
+            prop_assert!((actual - expected).abs() <= expected * 1e-12 + 1.0);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_verifier::{MockVerifier, MockVerifierRef};
+
+/// Minimal `IdentityVerifier` used to exercise identity-gated rounds in tests
+#[ink::contract]
+mod mock_verifier {
+    use ink::primitives::H160;
+    use qf_funding::IdentityVerifier;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MockVerifier {
+        scores: ink::storage::Mapping<H160, u32>, // Accounts with a score above zero count as verified
+    }
+
+    impl MockVerifier {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Set the score returned for an account
+        #[ink(message)]
+        pub fn set_score(&mut self, account: H160, score: u32) {
+            self.scores.insert(account, &score);
+        }
+    }
+
+    impl IdentityVerifier for MockVerifier {
+        #[ink(message)]
+        fn is_verified(&self, account: H160) -> bool {
+            self.scores.get(account).unwrap_or(0) > 0
+        }
+
+        #[ink(message)]
+        fn score(&self, account: H160) -> u32 {
+            self.scores.get(account).unwrap_or(0)
+        }
+    }
+}